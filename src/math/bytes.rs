@@ -0,0 +1,111 @@
+use std::{mem, slice};
+
+use crate::math::{matrix::Matrix, vector::Vector};
+
+// Zero-copy serialization to/from a raw byte buffer, for uploading to a GPU
+// buffer or other wire format. Relies on `Vector`/`Matrix` being `#[repr(C)]`
+// so their in-memory layout is just a contiguous array of `f32`s.
+pub trait Bytes {
+    fn write_bytes(&self, buffer: &mut [u8]);
+    fn byte_len(&self) -> usize;
+    fn as_slice(&self) -> &[f32];
+    fn from_bytes(bytes: &[u8]) -> Self;
+}
+
+impl<const N: usize, U> Bytes for Vector<N, U> {
+    fn write_bytes(&self, buffer: &mut [u8]) {
+        let bytes =
+            unsafe { slice::from_raw_parts(self as *const Self as *const u8, self.byte_len()) };
+        buffer[..bytes.len()].copy_from_slice(bytes);
+    }
+
+    fn byte_len(&self) -> usize {
+        mem::size_of::<Self>()
+    }
+
+    fn as_slice(&self) -> &[f32] {
+        &self.data
+    }
+
+    fn from_bytes(bytes: &[u8]) -> Self {
+        let mut result = Self::zeros();
+        let dst = unsafe {
+            slice::from_raw_parts_mut(&mut result as *mut Self as *mut u8, mem::size_of::<Self>())
+        };
+        dst.copy_from_slice(&bytes[..dst.len()]);
+        result
+    }
+}
+
+impl<const M: usize, const N: usize, Src, Dst> Bytes for Matrix<M, N, Src, Dst> {
+    fn write_bytes(&self, buffer: &mut [u8]) {
+        let bytes =
+            unsafe { slice::from_raw_parts(self as *const Self as *const u8, self.byte_len()) };
+        buffer[..bytes.len()].copy_from_slice(bytes);
+    }
+
+    fn byte_len(&self) -> usize {
+        mem::size_of::<Self>()
+    }
+
+    fn as_slice(&self) -> &[f32] {
+        unsafe { slice::from_raw_parts(self.data.as_ptr() as *const f32, M * N) }
+    }
+
+    fn from_bytes(bytes: &[u8]) -> Self {
+        let mut result = Self::zeros();
+        let dst = unsafe {
+            slice::from_raw_parts_mut(&mut result as *mut Self as *mut u8, mem::size_of::<Self>())
+        };
+        dst.copy_from_slice(&bytes[..dst.len()]);
+        result
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::math::{matrix3d::Matrix3D, vector3d::Vector3D};
+
+    #[test]
+    fn test_vector_byte_len() {
+        let v = Vector3D::new(1.0, 2.0, 3.0);
+        assert_eq!(v.byte_len(), 12);
+    }
+
+    #[test]
+    fn test_vector_as_slice() {
+        let v = Vector3D::new(1.0, 2.0, 3.0);
+        assert_eq!(v.as_slice(), &[1.0, 2.0, 3.0]);
+    }
+
+    #[test]
+    fn test_vector_round_trip() {
+        let v = Vector3D::new(1.0, 2.0, 3.0);
+        let mut buffer = [0u8; 12];
+        v.write_bytes(&mut buffer);
+        assert_eq!(Vector3D::from_bytes(&buffer), v);
+    }
+
+    #[test]
+    fn test_matrix_byte_len() {
+        // byte_len() doesn't pin the unit, so annotate to resolve it.
+        let m: Matrix3D = Matrix3D::identity();
+        assert_eq!(m.byte_len(), 36);
+    }
+
+    #[test]
+    fn test_matrix_as_slice() {
+        let m = Matrix3D::new(1.0, 2.0, 3.0, 4.0, 5.0, 6.0, 7.0, 8.0, 9.0);
+        // Column-major storage, so this is columns, not rows.
+        assert_eq!(m.as_slice(), &[1.0, 4.0, 7.0, 2.0, 5.0, 8.0, 3.0, 6.0, 9.0]);
+    }
+
+    #[test]
+    fn test_matrix_round_trip() {
+        let m = Matrix3D::new(1.0, 2.0, 3.0, 4.0, 5.0, 6.0, 7.0, 8.0, 9.0);
+        let mut buffer = [0u8; 36];
+        m.write_bytes(&mut buffer);
+        assert_eq!(Matrix3D::from_bytes(&buffer), m);
+    }
+}
@@ -0,0 +1,295 @@
+use std::{
+    fmt,
+    marker::PhantomData,
+    mem,
+    ops::{Add, AddAssign, Index, IndexMut, Mul, Sub, SubAssign},
+};
+
+use crate::math::vector::{Unknown, Vector};
+
+#[repr(C)]
+pub struct Matrix<const M: usize, const N: usize, Src = Unknown, Dst = Unknown> {
+    // Column-major order, matching Matrix3D:
+    // data[j] is the j-th column, an M-length array of rows.
+    pub data: [[f32; M]; N],
+    _unit: PhantomData<(Src, Dst)>,
+}
+
+impl<const M: usize, const N: usize, Src, Dst> Matrix<M, N, Src, Dst> {
+    pub fn zeros() -> Self {
+        Self {
+            data: [[0.0; M]; N],
+            _unit: PhantomData,
+        }
+    }
+
+    pub fn from_data(data: [[f32; M]; N]) -> Self {
+        Self {
+            data,
+            _unit: PhantomData,
+        }
+    }
+
+    /// Reinterprets this matrix as a transform between different spaces,
+    /// bypassing the compile-time space check.
+    pub fn cast_unit<Src2, Dst2>(self) -> Matrix<M, N, Src2, Dst2> {
+        Matrix {
+            data: self.data,
+            _unit: PhantomData,
+        }
+    }
+
+    /// Iterates in column-major order, matching the underlying storage.
+    pub fn iter(&self) -> impl Iterator<Item = &f32> {
+        self.data.iter().flatten()
+    }
+
+    /// Iterates in column-major order, matching the underlying storage.
+    pub fn iter_mut(&mut self) -> impl Iterator<Item = &mut f32> {
+        self.data.iter_mut().flatten()
+    }
+}
+
+impl<const M: usize, const N: usize, Src, Dst> Default for Matrix<M, N, Src, Dst> {
+    fn default() -> Self {
+        Self::zeros()
+    }
+}
+
+impl<const M: usize, const N: usize, Src, Dst> fmt::Debug for Matrix<M, N, Src, Dst> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Matrix").field("data", &self.data).finish()
+    }
+}
+
+impl<const M: usize, const N: usize, Src, Dst> PartialEq for Matrix<M, N, Src, Dst> {
+    fn eq(&self, other: &Self) -> bool {
+        self.data == other.data
+    }
+}
+
+impl<const N: usize, U> Matrix<N, N, U, U> {
+    pub fn identity() -> Self {
+        let mut result = Self::zeros();
+        for i in 0..N {
+            result[(i, i)] = 1.0;
+        }
+        result
+    }
+}
+
+// Converts (row, column) notation to column-major order.
+impl<const M: usize, const N: usize, Src, Dst> Index<(usize, usize)> for Matrix<M, N, Src, Dst> {
+    type Output = f32;
+
+    fn index(&self, (i, j): (usize, usize)) -> &Self::Output {
+        &self.data[j][i]
+    }
+}
+
+impl<const M: usize, const N: usize, Src, Dst> IndexMut<(usize, usize)> for Matrix<M, N, Src, Dst> {
+    fn index_mut(&mut self, (i, j): (usize, usize)) -> &mut Self::Output {
+        &mut self.data[j][i]
+    }
+}
+
+impl<const M: usize, const N: usize, Src, Dst> Index<usize> for Matrix<M, N, Src, Dst> {
+    type Output = Vector<M, Dst>;
+
+    fn index(&self, index: usize) -> &Self::Output {
+        unsafe { mem::transmute(&self.data[index]) }
+    }
+}
+
+impl<const M: usize, const N: usize, Src, Dst> IndexMut<usize> for Matrix<M, N, Src, Dst> {
+    fn index_mut(&mut self, index: usize) -> &mut Self::Output {
+        unsafe { mem::transmute(&mut self.data[index]) }
+    }
+}
+
+// Composes a Mid -> Dst transform with a Src -> Mid transform into a
+// Src -> Dst transform, the same way nalgebra/euclid chain transforms.
+impl<const M: usize, const N: usize, const P: usize, Src, Mid, Dst> Mul<Matrix<N, P, Src, Mid>>
+    for Matrix<M, N, Mid, Dst>
+{
+    type Output = Matrix<M, P, Src, Dst>;
+
+    fn mul(self, rhs: Matrix<N, P, Src, Mid>) -> Self::Output {
+        let mut result = Matrix::<M, P, Src, Dst>::zeros();
+        for i in 0..M {
+            for k in 0..P {
+                let mut sum = 0.0;
+                for j in 0..N {
+                    sum += self[(i, j)] * rhs[(j, k)];
+                }
+                result[(i, k)] = sum;
+            }
+        }
+        result
+    }
+}
+
+impl<const M: usize, const N: usize, Src, Dst> Mul<&Vector<N, Src>> for &Matrix<M, N, Src, Dst> {
+    type Output = Vector<M, Dst>;
+
+    fn mul(self, v: &Vector<N, Src>) -> Self::Output {
+        let mut result = Vector::<M, Dst>::zeros();
+        for i in 0..M {
+            let mut sum = 0.0;
+            for j in 0..N {
+                sum += self[(i, j)] * v[j];
+            }
+            result[i] = sum;
+        }
+        result
+    }
+}
+
+impl<const M: usize, const N: usize, Src, Dst> Mul<f32> for &Matrix<M, N, Src, Dst> {
+    type Output = Matrix<M, N, Src, Dst>;
+
+    fn mul(self, rhs: f32) -> Self::Output {
+        let mut result = Matrix::<M, N, Src, Dst>::zeros();
+        for j in 0..N {
+            for i in 0..M {
+                result.data[j][i] = self.data[j][i] * rhs;
+            }
+        }
+        result
+    }
+}
+
+impl<const M: usize, const N: usize, Src, Dst> Add for &Matrix<M, N, Src, Dst> {
+    type Output = Matrix<M, N, Src, Dst>;
+
+    fn add(self, other: &Matrix<M, N, Src, Dst>) -> Self::Output {
+        let mut result = Matrix::<M, N, Src, Dst>::zeros();
+        for j in 0..N {
+            for i in 0..M {
+                result.data[j][i] = self.data[j][i] + other.data[j][i];
+            }
+        }
+        result
+    }
+}
+
+impl<const M: usize, const N: usize, Src, Dst> Add for Matrix<M, N, Src, Dst> {
+    type Output = Matrix<M, N, Src, Dst>;
+
+    fn add(self, other: Matrix<M, N, Src, Dst>) -> Self::Output {
+        let mut result = Matrix::<M, N, Src, Dst>::zeros();
+        for j in 0..N {
+            for i in 0..M {
+                result.data[j][i] = self.data[j][i] + other.data[j][i];
+            }
+        }
+        result
+    }
+}
+
+impl<const M: usize, const N: usize, Src, Dst> Add<Matrix<M, N, Src, Dst>> for &Matrix<M, N, Src, Dst> {
+    type Output = Matrix<M, N, Src, Dst>;
+
+    fn add(self, other: Matrix<M, N, Src, Dst>) -> Self::Output {
+        let mut result = Matrix::<M, N, Src, Dst>::zeros();
+        for j in 0..N {
+            for i in 0..M {
+                result.data[j][i] = self.data[j][i] + other.data[j][i];
+            }
+        }
+        result
+    }
+}
+
+impl<const M: usize, const N: usize, Src, Dst> AddAssign for Matrix<M, N, Src, Dst> {
+    fn add_assign(&mut self, other: Matrix<M, N, Src, Dst>) {
+        for j in 0..N {
+            for i in 0..M {
+                self.data[j][i] += other.data[j][i];
+            }
+        }
+    }
+}
+
+impl<const M: usize, const N: usize, Src, Dst> Sub for &Matrix<M, N, Src, Dst> {
+    type Output = Matrix<M, N, Src, Dst>;
+
+    fn sub(self, other: &Matrix<M, N, Src, Dst>) -> Self::Output {
+        let mut result = Matrix::<M, N, Src, Dst>::zeros();
+        for j in 0..N {
+            for i in 0..M {
+                result.data[j][i] = self.data[j][i] - other.data[j][i];
+            }
+        }
+        result
+    }
+}
+
+impl<const M: usize, const N: usize, Src, Dst> Sub for Matrix<M, N, Src, Dst> {
+    type Output = Matrix<M, N, Src, Dst>;
+
+    fn sub(self, other: Matrix<M, N, Src, Dst>) -> Self::Output {
+        let mut result = Matrix::<M, N, Src, Dst>::zeros();
+        for j in 0..N {
+            for i in 0..M {
+                result.data[j][i] = self.data[j][i] - other.data[j][i];
+            }
+        }
+        result
+    }
+}
+
+impl<const M: usize, const N: usize, Src, Dst> Sub<Matrix<M, N, Src, Dst>> for &Matrix<M, N, Src, Dst> {
+    type Output = Matrix<M, N, Src, Dst>;
+
+    fn sub(self, other: Matrix<M, N, Src, Dst>) -> Self::Output {
+        let mut result = Matrix::<M, N, Src, Dst>::zeros();
+        for j in 0..N {
+            for i in 0..M {
+                result.data[j][i] = self.data[j][i] - other.data[j][i];
+            }
+        }
+        result
+    }
+}
+
+impl<const M: usize, const N: usize, Src, Dst> SubAssign for Matrix<M, N, Src, Dst> {
+    fn sub_assign(&mut self, other: Matrix<M, N, Src, Dst>) {
+        for j in 0..N {
+            for i in 0..M {
+                self.data[j][i] -= other.data[j][i];
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_identity_4x4() {
+        let m: Matrix<4, 4> = Matrix::identity();
+        for i in 0..4 {
+            for j in 0..4 {
+                assert_eq!(m[(i, j)], if i == j { 1.0 } else { 0.0 });
+            }
+        }
+    }
+
+    #[test]
+    fn test_mul_2x2() {
+        let a: Matrix<2, 2> = Matrix::from_data([[1.0, 3.0], [2.0, 4.0]]);
+        let b: Matrix<2, 2> = Matrix::from_data([[5.0, 7.0], [6.0, 8.0]]);
+        let expected: Matrix<2, 2> = Matrix::from_data([[19.0, 43.0], [22.0, 50.0]]);
+        assert_eq!(a * b, expected);
+    }
+
+    #[test]
+    fn test_mul_vector_2x2() {
+        let m: Matrix<2, 2> = Matrix::from_data([[1.0, 3.0], [2.0, 4.0]]);
+        let v: Vector<2> = Vector::from_data([1.0, 1.0]);
+        let expected: Vector<2> = Vector::from_data([3.0, 7.0]);
+        assert_eq!(&m * &v, expected);
+    }
+}
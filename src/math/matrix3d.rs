@@ -1,19 +1,18 @@
-use std::{
-    mem,
-    ops::{Index, IndexMut, Mul},
-};
-
-use crate::math::vector3d::Vector3D;
-
-#[derive(Debug, PartialEq)]
-pub struct Matrix3D {
-    // Column-major order,
-    // so n[0] is the first column,
-    // n[2][1] is the third column, second row etc.
-    pub n: [[f32; 3]; 3],
-}
+use crate::math::{matrix::Matrix, vector::Unknown, vector3d::Vector3D};
+
+// Below this, a matrix is considered singular and has no inverse.
+const INVERSE_EPSILON: f32 = 1e-6;
 
-impl Matrix3D {
+pub type Matrix3D<Src = Unknown, Dst = Unknown> = Matrix<3, 3, Src, Dst>;
+
+// A type alias's default type parameter only kicks in when the alias is
+// written out explicitly, never during call-site inference, so an
+// unannotated `Matrix3D::new(...)` needs a concrete, non-generic home to
+// resolve to `Src = Dst = Unknown`. `identity()` stays on the generic
+// `Matrix<N, N, U, U>` in matrix.rs (an inherent impl here would collide
+// with it at `Src = Dst = Unknown`), so callers that don't otherwise pin
+// the unit need an explicit `Matrix3D` type annotation.
+impl Matrix3D<Unknown, Unknown> {
     #[allow(clippy::too_many_arguments)]
     pub fn new(
         n00: f32,
@@ -26,66 +25,74 @@ impl Matrix3D {
         n21: f32,
         n22: f32,
     ) -> Self {
-        Self {
-            n: [[n00, n10, n20], [n01, n11, n21], [n02, n12, n22]],
-        }
+        Self::from_data([[n00, n10, n20], [n01, n11, n21], [n02, n12, n22]])
     }
+}
 
-    pub fn from_vectors(v1: &Vector3D, v2: &Vector3D, v3: &Vector3D) -> Self {
-        Self {
-            n: [
-                [v1[0], v1[1], v1[2]],
-                [v2[0], v2[1], v2[2]],
-                [v3[0], v3[1], v3[2]],
-            ],
-        }
+impl<Src, Dst> Matrix3D<Src, Dst> {
+    pub fn from_vectors(v1: &Vector3D<Dst>, v2: &Vector3D<Dst>, v3: &Vector3D<Dst>) -> Self {
+        Self::from_data([
+            [v1[0], v1[1], v1[2]],
+            [v2[0], v2[1], v2[2]],
+            [v3[0], v3[1], v3[2]],
+        ])
     }
-}
 
-// Converts (x,y) row-column order notation to column-major order
-impl Index<(usize, usize)> for Matrix3D {
-    type Output = f32;
+    pub fn transpose(&self) -> Matrix3D<Src, Dst> {
+        Self::from_data([
+            [self.data[0][0], self.data[1][0], self.data[2][0]],
+            [self.data[0][1], self.data[1][1], self.data[2][1]],
+            [self.data[0][2], self.data[1][2], self.data[2][2]],
+        ])
+    }
 
-    fn index(&self, (i, j): (usize, usize)) -> &Self::Output {
-        &self.n[j][i]
+    /// The i-th row, as the coefficients applied to each input basis vector.
+    pub fn row(&self, i: usize) -> Vector3D<Src> {
+        Vector3D::from_data([self[(i, 0)], self[(i, 1)], self[(i, 2)]])
     }
-}
 
-impl IndexMut<(usize, usize)> for Matrix3D {
-    fn index_mut(&mut self, (i, j): (usize, usize)) -> &mut Self::Output {
-        &mut self.n[j][i]
+    /// The j-th column, i.e. where the j-th input basis vector is sent.
+    pub fn column(&self, j: usize) -> Vector3D<Dst> {
+        Vector3D::from_data([self[j][0], self[j][1], self[j][2]])
     }
-}
 
-impl Index<usize> for Matrix3D {
-    type Output = Vector3D;
+    pub fn iter_rows(&self) -> [Vector3D<Src>; 3] {
+        [self.row(0), self.row(1), self.row(2)]
+    }
 
-    fn index(&self, index: usize) -> &Self::Output {
-        unsafe { mem::transmute(&self.n[index]) }
+    pub fn iter_columns(&self) -> [Vector3D<Dst>; 3] {
+        [self.column(0), self.column(1), self.column(2)]
     }
-}
 
-impl IndexMut<usize> for Matrix3D {
-    fn index_mut(&mut self, index: usize) -> &mut Self::Output {
-        unsafe { mem::transmute(&mut self.n[index]) }
+    pub fn determinant(&self) -> f32 {
+        let a = &self[0];
+        let b = &self[1];
+        let c = &self[2];
+        a.dot(&b.cross(c))
     }
-}
 
-impl Mul<Matrix3D> for Matrix3D {
-    type Output = Matrix3D;
-
-    fn mul(self, rhs: Matrix3D) -> Self::Output {
-        Matrix3D::new(
-            self.n[0][0] * rhs.n[0][0] + self.n[1][0] * rhs.n[0][1] + self.n[2][0] * rhs.n[0][2],
-            self.n[0][0] * rhs.n[1][0] + self.n[1][0] * rhs.n[1][1] + self.n[2][0] * rhs.n[1][2],
-            self.n[0][0] * rhs.n[2][0] + self.n[1][0] * rhs.n[2][1] + self.n[2][0] * rhs.n[2][2],
-            self.n[0][1] * rhs.n[0][0] + self.n[1][1] * rhs.n[0][1] + self.n[2][1] * rhs.n[0][2],
-            self.n[0][1] * rhs.n[1][0] + self.n[1][1] * rhs.n[1][1] + self.n[2][1] * rhs.n[1][2],
-            self.n[0][1] * rhs.n[2][0] + self.n[1][1] * rhs.n[2][1] + self.n[2][1] * rhs.n[2][2],
-            self.n[0][2] * rhs.n[0][0] + self.n[1][2] * rhs.n[0][1] + self.n[2][2] * rhs.n[0][2],
-            self.n[0][2] * rhs.n[1][0] + self.n[1][2] * rhs.n[1][1] + self.n[2][2] * rhs.n[1][2],
-            self.n[0][2] * rhs.n[2][0] + self.n[1][2] * rhs.n[2][1] + self.n[2][2] * rhs.n[2][2],
-        )
+    // The inverse of a Src -> Dst transform is a Dst -> Src transform.
+    pub fn inverse(&self) -> Option<Matrix3D<Dst, Src>> {
+        let a = &self[0];
+        let b = &self[1];
+        let c = &self[2];
+
+        let det = a.dot(&b.cross(c));
+        if det.abs() < INVERSE_EPSILON {
+            return None;
+        }
+
+        let r0 = b.cross(c);
+        let r1 = c.cross(a);
+        let r2 = a.cross(b);
+
+        let mut result = Matrix3D::<Dst, Src>::zeros();
+        for j in 0..3 {
+            result[(0, j)] = r0[j] / det;
+            result[(1, j)] = r1[j] / det;
+            result[(2, j)] = r2[j] / det;
+        }
+        Some(result)
     }
 }
 
@@ -93,6 +100,22 @@ impl Mul<Matrix3D> for Matrix3D {
 mod tests {
     use super::*;
 
+    // Two arbitrary spaces, used to check that from_vectors types its
+    // columns as the output space (Dst), not the input space (Src).
+    pub struct WorldSpace;
+    pub struct LocalSpace;
+
+    #[test]
+    fn test_from_vectors() {
+        let v1: Vector3D<WorldSpace> = Vector3D::from_data([1.0, 0.0, 0.0]);
+        let v2: Vector3D<WorldSpace> = Vector3D::from_data([0.0, 1.0, 0.0]);
+        let v3: Vector3D<WorldSpace> = Vector3D::from_data([0.0, 0.0, 1.0]);
+        let m: Matrix3D<LocalSpace, WorldSpace> = Matrix3D::from_vectors(&v1, &v2, &v3);
+        assert_eq!(m[0], Vector3D::from_data([1.0, 0.0, 0.0]));
+        assert_eq!(m[1], Vector3D::from_data([0.0, 1.0, 0.0]));
+        assert_eq!(m[2], Vector3D::from_data([0.0, 0.0, 1.0]));
+    }
+
     #[test]
     fn test_index() {
         let m = Matrix3D::new(1.0, 2.0, 3.0, 4.0, 5.0, 6.0, 7.0, 8.0, 9.0);
@@ -146,4 +169,173 @@ mod tests {
         assert_eq!(m3[1], Vector3D::new(5.0, 11.0, -15.0));
         assert_eq!(m3[2], Vector3D::new(10.0, 1.0, 5.0));
     }
+
+    #[test]
+    fn test_identity() {
+        // Nothing here ties the unit to anything else, so it needs an
+        // explicit annotation to resolve `Src`/`Dst` to `Unknown`.
+        let m: Matrix3D = Matrix3D::identity();
+        assert_eq!(m[0], Vector3D::new(1.0, 0.0, 0.0));
+        assert_eq!(m[1], Vector3D::new(0.0, 1.0, 0.0));
+        assert_eq!(m[2], Vector3D::new(0.0, 0.0, 1.0));
+    }
+
+    #[test]
+    fn test_transpose() {
+        let m = Matrix3D::new(1.0, 2.0, 3.0, 4.0, 5.0, 6.0, 7.0, 8.0, 9.0);
+        let t = m.transpose();
+        assert_eq!(t, Matrix3D::new(1.0, 4.0, 7.0, 2.0, 5.0, 8.0, 3.0, 6.0, 9.0));
+    }
+
+    #[test]
+    fn test_mul_vector() {
+        let m = Matrix3D::identity();
+        let v = Vector3D::new(1.0, 2.0, 3.0);
+        assert_eq!(&m * &v, Vector3D::new(1.0, 2.0, 3.0));
+
+        let m2 = Matrix3D::new(1.0, 2.0, 3.0, 4.0, 5.0, 6.0, 7.0, 8.0, 9.0);
+        assert_eq!(&m2 * &v, Vector3D::new(14.0, 32.0, 50.0));
+    }
+
+    #[test]
+    fn test_mul_scalar() {
+        let m = Matrix3D::new(1.0, 2.0, 3.0, 4.0, 5.0, 6.0, 7.0, 8.0, 9.0);
+        let scaled = &m * 2.0;
+        assert_eq!(
+            scaled,
+            Matrix3D::new(2.0, 4.0, 6.0, 8.0, 10.0, 12.0, 14.0, 16.0, 18.0)
+        );
+    }
+
+    #[test]
+    fn test_add() {
+        let m1 = Matrix3D::new(1.0, 2.0, 3.0, 4.0, 5.0, 6.0, 7.0, 8.0, 9.0);
+        let m2 = Matrix3D::identity();
+        assert_eq!(
+            &m1 + &m2,
+            Matrix3D::new(2.0, 2.0, 3.0, 4.0, 6.0, 6.0, 7.0, 8.0, 10.0)
+        );
+        assert_eq!(
+            Matrix3D::new(1.0, 2.0, 3.0, 4.0, 5.0, 6.0, 7.0, 8.0, 9.0) + Matrix3D::identity(),
+            Matrix3D::new(2.0, 2.0, 3.0, 4.0, 6.0, 6.0, 7.0, 8.0, 10.0)
+        );
+        assert_eq!(
+            &m1 + m2,
+            Matrix3D::new(2.0, 2.0, 3.0, 4.0, 6.0, 6.0, 7.0, 8.0, 10.0)
+        );
+    }
+
+    #[test]
+    fn test_sub() {
+        let m1 = Matrix3D::new(2.0, 2.0, 3.0, 4.0, 6.0, 6.0, 7.0, 8.0, 10.0);
+        let m2 = Matrix3D::identity();
+        assert_eq!(
+            &m1 - &m2,
+            Matrix3D::new(1.0, 2.0, 3.0, 4.0, 5.0, 6.0, 7.0, 8.0, 9.0)
+        );
+        assert_eq!(
+            m1 - m2,
+            Matrix3D::new(1.0, 2.0, 3.0, 4.0, 5.0, 6.0, 7.0, 8.0, 9.0)
+        );
+    }
+
+    #[test]
+    fn test_add_assign() {
+        let mut m = Matrix3D::new(1.0, 2.0, 3.0, 4.0, 5.0, 6.0, 7.0, 8.0, 9.0);
+        m += Matrix3D::identity();
+        assert_eq!(m, Matrix3D::new(2.0, 2.0, 3.0, 4.0, 6.0, 6.0, 7.0, 8.0, 10.0));
+    }
+
+    #[test]
+    fn test_sub_assign() {
+        let mut m = Matrix3D::new(2.0, 2.0, 3.0, 4.0, 6.0, 6.0, 7.0, 8.0, 10.0);
+        m -= Matrix3D::identity();
+        assert_eq!(m, Matrix3D::new(1.0, 2.0, 3.0, 4.0, 5.0, 6.0, 7.0, 8.0, 9.0));
+    }
+
+    #[test]
+    fn test_determinant() {
+        // determinant() doesn't pin the unit either, so annotate here too.
+        let m: Matrix3D = Matrix3D::identity();
+        assert_eq!(m.determinant(), 1.0);
+
+        let m2 = Matrix3D::new(1.0, 3.0, -2.0, 0.0, -1.0, 4.0, 4.0, -3.0, 2.0);
+        assert_eq!(m2.determinant(), 50.0);
+    }
+
+    fn assert_matrix_approx_eq<Src, Dst>(a: &Matrix3D<Src, Dst>, b: &Matrix3D<Src, Dst>) {
+        for i in 0..3 {
+            for j in 0..3 {
+                assert!(
+                    (a[(i, j)] - b[(i, j)]).abs() < 1e-4,
+                    "a[({i}, {j})] = {}, b[({i}, {j})] = {}",
+                    a[(i, j)],
+                    b[(i, j)]
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_inverse() {
+        let m = Matrix3D::new(1.0, 3.0, -2.0, 0.0, -1.0, 4.0, 4.0, -3.0, 2.0);
+        let inv = m.inverse().unwrap();
+        assert_matrix_approx_eq(&(m * inv), &Matrix3D::identity());
+    }
+
+    #[test]
+    fn test_inverse_singular() {
+        let m = Matrix3D::new(1.0, 2.0, 3.0, 2.0, 4.0, 6.0, 3.0, 6.0, 9.0);
+        assert_eq!(m.inverse(), None);
+    }
+
+    #[test]
+    fn test_iter() {
+        let m = Matrix3D::new(1.0, 2.0, 3.0, 4.0, 5.0, 6.0, 7.0, 8.0, 9.0);
+        // Column-major order, matching storage: each column in turn.
+        let values: Vec<f32> = m.iter().copied().collect();
+        assert_eq!(values, vec![1.0, 4.0, 7.0, 2.0, 5.0, 8.0, 3.0, 6.0, 9.0]);
+    }
+
+    #[test]
+    fn test_iter_mut() {
+        let mut m = Matrix3D::new(1.0, 2.0, 3.0, 4.0, 5.0, 6.0, 7.0, 8.0, 9.0);
+        for v in m.iter_mut() {
+            *v *= 2.0;
+        }
+        assert_eq!(m, Matrix3D::new(2.0, 4.0, 6.0, 8.0, 10.0, 12.0, 14.0, 16.0, 18.0));
+    }
+
+    #[test]
+    fn test_row_and_column() {
+        let m = Matrix3D::new(1.0, 2.0, 3.0, 4.0, 5.0, 6.0, 7.0, 8.0, 9.0);
+        assert_eq!(m.row(0), Vector3D::new(1.0, 2.0, 3.0));
+        assert_eq!(m.row(1), Vector3D::new(4.0, 5.0, 6.0));
+        assert_eq!(m.row(2), Vector3D::new(7.0, 8.0, 9.0));
+
+        assert_eq!(m.column(0), Vector3D::new(1.0, 4.0, 7.0));
+        assert_eq!(m.column(1), Vector3D::new(2.0, 5.0, 8.0));
+        assert_eq!(m.column(2), Vector3D::new(3.0, 6.0, 9.0));
+    }
+
+    #[test]
+    fn test_iter_rows_and_columns() {
+        let m = Matrix3D::new(1.0, 2.0, 3.0, 4.0, 5.0, 6.0, 7.0, 8.0, 9.0);
+        assert_eq!(
+            m.iter_rows(),
+            [
+                Vector3D::new(1.0, 2.0, 3.0),
+                Vector3D::new(4.0, 5.0, 6.0),
+                Vector3D::new(7.0, 8.0, 9.0),
+            ]
+        );
+        assert_eq!(
+            m.iter_columns(),
+            [
+                Vector3D::new(1.0, 4.0, 7.0),
+                Vector3D::new(2.0, 5.0, 8.0),
+                Vector3D::new(3.0, 6.0, 9.0),
+            ]
+        );
+    }
 }
@@ -1,218 +1,233 @@
-use std::ops::{Add, Div, DivAssign, Index, IndexMut, Mul, MulAssign, Sub};
-
-#[derive(Debug, PartialEq)]
-pub struct Vector {
-    x: f32,
-    y: f32,
-    z: f32,
+use std::{
+    fmt,
+    marker::PhantomData,
+    ops::{
+        Add, AddAssign, Div, DivAssign, Index, IndexMut, Mul, MulAssign, Neg, Sub, SubAssign,
+    },
+};
+
+/// Default unit for callers that don't need compile-time space safety.
+pub struct Unknown;
+
+#[repr(C)]
+pub struct Vector<const N: usize, U = Unknown> {
+    pub data: [f32; N],
+    _unit: PhantomData<U>,
 }
 
-#[allow(dead_code)]
-impl Vector {
-    fn new(x: f32, y: f32, z: f32) -> Self {
-        Self { x, y, z }
+impl<const N: usize, U> Vector<N, U> {
+    pub fn zeros() -> Self {
+        Self {
+            data: [0.0; N],
+            _unit: PhantomData,
+        }
     }
-}
 
-impl Add for &Vector {
-    type Output = Vector;
+    pub fn from_data(data: [f32; N]) -> Self {
+        Self {
+            data,
+            _unit: PhantomData,
+        }
+    }
 
-    fn add(self, other: &Vector) -> Vector {
+    /// Reinterprets this vector as belonging to a different unit, bypassing
+    /// the compile-time space check.
+    pub fn cast_unit<V>(self) -> Vector<N, V> {
         Vector {
-            x: self.x + other.x,
-            y: self.y + other.y,
-            z: self.z + other.z,
+            data: self.data,
+            _unit: PhantomData,
         }
     }
 }
 
-impl Sub for Vector {
-    type Output = Vector;
+impl<const N: usize, U> Default for Vector<N, U> {
+    fn default() -> Self {
+        Self::zeros()
+    }
+}
 
-    fn sub(self, other: Vector) -> Vector {
-        Vector {
-            x: self.x - other.x,
-            y: self.y - other.y,
-            z: self.z - other.z,
-        }
+impl<const N: usize, U> fmt::Debug for Vector<N, U> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Vector").field("data", &self.data).finish()
     }
 }
 
-impl Mul<f32> for &Vector {
-    type Output = Vector;
+impl<const N: usize, U> PartialEq for Vector<N, U> {
+    fn eq(&self, other: &Self) -> bool {
+        self.data == other.data
+    }
+}
 
-    fn mul(self, rhs: f32) -> Self::Output {
-        Vector {
-            x: self.x * rhs,
-            y: self.y * rhs,
-            z: self.z * rhs,
+impl<const N: usize, U> Add for &Vector<N, U> {
+    type Output = Vector<N, U>;
+
+    fn add(self, other: &Vector<N, U>) -> Vector<N, U> {
+        let mut result = Vector::zeros();
+        for i in 0..N {
+            result.data[i] = self.data[i] + other.data[i];
         }
+        result
     }
 }
 
-impl MulAssign<f32> for Vector {
-    fn mul_assign(&mut self, rhs: f32) {
-        self.x *= rhs;
-        self.y *= rhs;
-        self.z *= rhs;
+impl<const N: usize, U> Add for Vector<N, U> {
+    type Output = Vector<N, U>;
+
+    fn add(self, other: Vector<N, U>) -> Vector<N, U> {
+        let mut result = Vector::zeros();
+        for i in 0..N {
+            result.data[i] = self.data[i] + other.data[i];
+        }
+        result
     }
 }
 
-impl Div<f32> for &Vector {
-    type Output = Vector;
+impl<const N: usize, U> Add<Vector<N, U>> for &Vector<N, U> {
+    type Output = Vector<N, U>;
 
-    fn div(self, rhs: f32) -> Self::Output {
-        Vector {
-            x: self.x / rhs,
-            y: self.y / rhs,
-            z: self.z / rhs,
+    fn add(self, other: Vector<N, U>) -> Vector<N, U> {
+        let mut result = Vector::zeros();
+        for i in 0..N {
+            result.data[i] = self.data[i] + other.data[i];
         }
+        result
     }
 }
 
-impl DivAssign<f32> for Vector {
-    fn div_assign(&mut self, rhs: f32) {
-        self.x /= rhs;
-        self.y /= rhs;
-        self.z /= rhs;
+impl<const N: usize, U> AddAssign for Vector<N, U> {
+    fn add_assign(&mut self, other: Vector<N, U>) {
+        for i in 0..N {
+            self.data[i] += other.data[i];
+        }
     }
 }
 
-impl Index<usize> for Vector {
-    type Output = f32;
+impl<const N: usize, U> Sub for &Vector<N, U> {
+    type Output = Vector<N, U>;
 
-    fn index(&self, index: usize) -> &Self::Output {
-        match index {
-            0 => &self.x,
-            1 => &self.y,
-            2 => &self.z,
-            _ => panic!("Index out of bounds"),
+    fn sub(self, other: &Vector<N, U>) -> Vector<N, U> {
+        let mut result = Vector::zeros();
+        for i in 0..N {
+            result.data[i] = self.data[i] - other.data[i];
         }
+        result
     }
 }
 
-impl IndexMut<usize> for Vector {
-    fn index_mut(&mut self, index: usize) -> &mut Self::Output {
-        match index {
-            0 => &mut self.x,
-            1 => &mut self.y,
-            2 => &mut self.z,
-            _ => panic!("Index out of bounds"),
+impl<const N: usize, U> Sub for Vector<N, U> {
+    type Output = Vector<N, U>;
+
+    fn sub(self, other: Vector<N, U>) -> Vector<N, U> {
+        let mut result = Vector::zeros();
+        for i in 0..N {
+            result.data[i] = self.data[i] - other.data[i];
         }
+        result
     }
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+impl<const N: usize, U> Sub<Vector<N, U>> for &Vector<N, U> {
+    type Output = Vector<N, U>;
 
-    #[test]
-    fn test_add() {
-        let v1 = Vector::new(1.0, 2.0, 3.0);
-        let v2 = Vector::new(4.0, 5.0, 6.0);
-        let v3 = &v1 + &v2;
-        assert_eq!(v3.x, 5.0);
-        assert_eq!(v3.y, 7.0);
-        assert_eq!(v3.z, 9.0);
+    fn sub(self, other: Vector<N, U>) -> Vector<N, U> {
+        let mut result = Vector::zeros();
+        for i in 0..N {
+            result.data[i] = self.data[i] - other.data[i];
+        }
+        result
     }
+}
 
-    #[test]
-    fn test_sub() {
-        let v1 = Vector::new(1.0, 2.0, 3.0);
-        let v2 = Vector::new(4.0, 5.0, 6.0);
-        let v3 = v1 - v2;
-        assert_eq!(v3.x, -3.0);
-        assert_eq!(v3.y, -3.0);
-        assert_eq!(v3.z, -3.0);
+impl<const N: usize, U> SubAssign for Vector<N, U> {
+    fn sub_assign(&mut self, other: Vector<N, U>) {
+        for i in 0..N {
+            self.data[i] -= other.data[i];
+        }
     }
+}
 
-    #[test]
-    fn test_mul() {
-        let v1 = Vector::new(1.0, 2.0, 3.0);
-        let v2 = &v1 * 2.0;
-        assert_eq!(v2.x, 2.0);
-        assert_eq!(v2.y, 4.0);
-        assert_eq!(v2.z, 6.0);
+impl<const N: usize, U> Neg for Vector<N, U> {
+    type Output = Vector<N, U>;
+
+    fn neg(self) -> Self::Output {
+        let mut result = Vector::zeros();
+        for i in 0..N {
+            result.data[i] = -self.data[i];
+        }
+        result
     }
+}
 
-    #[test]
-    fn test_mul_assign() {
-        let mut v1 = Vector::new(1.0, 2.0, 3.0);
-        v1 *= 2.0;
-        assert_eq!(v1.x, 2.0);
-        assert_eq!(v1.y, 4.0);
-        assert_eq!(v1.z, 6.0);
+impl<const N: usize, U> Mul<f32> for &Vector<N, U> {
+    type Output = Vector<N, U>;
+
+    fn mul(self, rhs: f32) -> Self::Output {
+        let mut result = Vector::zeros();
+        for i in 0..N {
+            result.data[i] = self.data[i] * rhs;
+        }
+        result
     }
+}
 
-    #[test]
-    fn test_div() {
-        let v1 = Vector::new(1.0, 2.0, 3.0);
-        let v2 = &v1 / 2.0;
-        assert_eq!(v2.x, 0.5);
-        assert_eq!(v2.y, 1.0);
-        assert_eq!(v2.z, 1.5);
+impl<const N: usize, U> MulAssign<f32> for Vector<N, U> {
+    fn mul_assign(&mut self, rhs: f32) {
+        for v in self.data.iter_mut() {
+            *v *= rhs;
+        }
     }
+}
 
-    #[test]
-    fn test_div_by_zero() {
-        let v1 = Vector::new(1.0, 2.0, 3.0);
-        let v2 = &v1 / 0.0;
+impl<const N: usize, U> Div<f32> for &Vector<N, U> {
+    type Output = Vector<N, U>;
 
-        // Going by IEEE 754, dividing by zero results in infinity
-        assert_eq!(v2.x, f32::INFINITY);
-        assert_eq!(v2.y, f32::INFINITY);
-        assert_eq!(v2.z, f32::INFINITY);
+    fn div(self, rhs: f32) -> Self::Output {
+        let mut result = Vector::zeros();
+        for i in 0..N {
+            result.data[i] = self.data[i] / rhs;
+        }
+        result
     }
+}
 
-    #[test]
-    fn test_div_assign() {
-        let mut v1 = Vector::new(1.0, 2.0, 3.0);
-        v1 /= 2.0;
-        assert_eq!(v1.x, 0.5);
-        assert_eq!(v1.y, 1.0);
-        assert_eq!(v1.z, 1.5);
+impl<const N: usize, U> DivAssign<f32> for Vector<N, U> {
+    fn div_assign(&mut self, rhs: f32) {
+        for v in self.data.iter_mut() {
+            *v /= rhs;
+        }
     }
+}
 
-    #[test]
-    fn test_index() {
-        let v = Vector::new(1.0, 2.0, 3.0);
-        assert_eq!(v[0], 1.0);
-        assert_eq!(v[1], 2.0);
-        assert_eq!(v[2], 3.0);
-    }
+impl<const N: usize, U> Index<usize> for Vector<N, U> {
+    type Output = f32;
 
-    #[test]
-    #[should_panic]
-    fn test_index_out_of_bounds() {
-        let v = Vector::new(1.0, 2.0, 3.0);
-        let _ = v[3];
+    fn index(&self, index: usize) -> &Self::Output {
+        &self.data[index]
     }
+}
 
-    #[test]
-    fn test_index_mut() {
-        let mut v = Vector::new(1.0, 2.0, 3.0);
-        v[0] = 4.0;
-        assert_eq!(v[0], 4.0);
+impl<const N: usize, U> IndexMut<usize> for Vector<N, U> {
+    fn index_mut(&mut self, index: usize) -> &mut Self::Output {
+        &mut self.data[index]
     }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
 
     #[test]
-    #[should_panic]
-    fn test_index_mut_out_of_bounds() {
-        let mut v = Vector::new(1.0, 2.0, 3.0);
-        v[3] = 4.0;
+    fn test_add_n4() {
+        let v1: Vector<4> = Vector::from_data([1.0, 2.0, 3.0, 4.0]);
+        let v2: Vector<4> = Vector::from_data([5.0, 6.0, 7.0, 8.0]);
+        let expected: Vector<4> = Vector::from_data([6.0, 8.0, 10.0, 12.0]);
+        assert_eq!(&v1 + &v2, expected);
     }
 
     #[test]
-    fn test_operator_chaining() {
-        let v1 = Vector::new(1.0, 2.0, 3.0);
-        let v2 = Vector::new(4.0, 5.0, 6.0);
-        let v3 = &v1 + &v2;
-        let v4 = &v3 * 2.0;
-        assert_eq!(v4.x, 10.0);
-        assert_eq!(v4.y, 14.0);
-        assert_eq!(v4.z, 18.0);
-
-        let v5 = &(&v1 + &v2) * 2.0;
-        assert_eq!(v5, v4);
+    fn test_index_n4() {
+        let v: Vector<4> = Vector::from_data([1.0, 2.0, 3.0, 4.0]);
+        assert_eq!(v[0], 1.0);
+        assert_eq!(v[3], 4.0);
     }
 }
@@ -1,123 +1,86 @@
-use std::ops::{Add, Div, DivAssign, Index, IndexMut, Mul, MulAssign, Neg, Sub};
+use crate::math::vector::{Unknown, Vector};
 
-#[derive(Debug, PartialEq)]
-pub struct Vector3D {
-    pub x: f32,
-    pub y: f32,
-    pub z: f32,
-}
+pub type Vector3D<U = Unknown> = Vector<3, U>;
 
-impl Vector3D {
+// A type alias's default type parameter only kicks in when the alias is
+// written out explicitly (e.g. `let v: Vector3D = ...`), never during call-site
+// inference, so an unannotated `Vector3D::new(...)` needs a concrete,
+// non-generic home to resolve to `U = Unknown`. Unit-tagged construction
+// goes through `from_data` instead.
+impl Vector3D<Unknown> {
     pub fn new(x: f32, y: f32, z: f32) -> Self {
-        Self { x, y, z }
+        Self::from_data([x, y, z])
     }
+}
 
-    pub fn magnitude(&self) -> f32 {
-        (self.x * self.x + self.y * self.y + self.z * self.z).sqrt()
+impl<U> Vector3D<U> {
+    pub fn x(&self) -> f32 {
+        self.data[0]
     }
 
-    pub fn normalize(&self) -> Self {
-        self / self.magnitude()
+    pub fn y(&self) -> f32 {
+        self.data[1]
     }
-}
-
-impl Add for &Vector3D {
-    type Output = Vector3D;
 
-    fn add(self, other: &Vector3D) -> Vector3D {
-        Vector3D {
-            x: self.x + other.x,
-            y: self.y + other.y,
-            z: self.z + other.z,
-        }
+    pub fn z(&self) -> f32 {
+        self.data[2]
     }
-}
-
-impl Sub for Vector3D {
-    type Output = Vector3D;
 
-    fn sub(self, other: Vector3D) -> Vector3D {
-        Vector3D {
-            x: self.x - other.x,
-            y: self.y - other.y,
-            z: self.z - other.z,
-        }
+    pub fn magnitude(&self) -> f32 {
+        (self.x() * self.x() + self.y() * self.y() + self.z() * self.z()).sqrt()
     }
-}
-
-impl Neg for Vector3D {
-    type Output = Vector3D;
 
-    fn neg(self) -> Self::Output {
-        Vector3D {
-            x: -self.x,
-            y: -self.y,
-            z: -self.z,
-        }
+    pub fn normalize(&self) -> Self {
+        self / self.magnitude()
     }
-}
-
-impl Mul<f32> for &Vector3D {
-    type Output = Vector3D;
 
-    fn mul(self, rhs: f32) -> Self::Output {
-        Vector3D {
-            x: self.x * rhs,
-            y: self.y * rhs,
-            z: self.z * rhs,
-        }
+    pub fn dot(&self, other: &Vector3D<U>) -> f32 {
+        self.x() * other.x() + self.y() * other.y() + self.z() * other.z()
     }
-}
 
-impl MulAssign<f32> for Vector3D {
-    fn mul_assign(&mut self, rhs: f32) {
-        self.x *= rhs;
-        self.y *= rhs;
-        self.z *= rhs;
+    pub fn cross(&self, other: &Vector3D<U>) -> Vector3D<U> {
+        Self::from_data([
+            self.y() * other.z() - self.z() * other.y(),
+            self.z() * other.x() - self.x() * other.z(),
+            self.x() * other.y() - self.y() * other.x(),
+        ])
     }
-}
 
-impl Div<f32> for &Vector3D {
-    type Output = Vector3D;
+    pub fn distance_squared(&self, other: &Vector3D<U>) -> f32 {
+        let dx = self.x() - other.x();
+        let dy = self.y() - other.y();
+        let dz = self.z() - other.z();
+        dx * dx + dy * dy + dz * dz
+    }
 
-    fn div(self, rhs: f32) -> Self::Output {
-        Vector3D {
-            x: self.x / rhs,
-            y: self.y / rhs,
-            z: self.z / rhs,
-        }
+    pub fn distance(&self, other: &Vector3D<U>) -> f32 {
+        self.distance_squared(other).sqrt()
     }
-}
 
-impl DivAssign<f32> for Vector3D {
-    fn div_assign(&mut self, rhs: f32) {
-        self.x /= rhs;
-        self.y /= rhs;
-        self.z /= rhs;
+    pub fn angle_between(&self, other: &Vector3D<U>) -> f32 {
+        let cos_theta = (self.dot(other) / (self.magnitude() * other.magnitude())).clamp(-1.0, 1.0);
+        cos_theta.acos()
     }
-}
 
-impl Index<usize> for Vector3D {
-    type Output = f32;
+    pub fn project_onto(&self, other: &Vector3D<U>) -> Vector3D<U> {
+        other * (self.dot(other) / other.dot(other))
+    }
 
-    fn index(&self, index: usize) -> &Self::Output {
-        match index {
-            0 => &self.x,
-            1 => &self.y,
-            2 => &self.z,
-            _ => panic!("Index out of bounds"),
-        }
+    pub fn reflect(&self, normal: &Vector3D<U>) -> Vector3D<U> {
+        let factor = 2.0 * self.dot(normal);
+        Self::from_data([
+            self.x() - factor * normal.x(),
+            self.y() - factor * normal.y(),
+            self.z() - factor * normal.z(),
+        ])
     }
-}
 
-impl IndexMut<usize> for Vector3D {
-    fn index_mut(&mut self, index: usize) -> &mut Self::Output {
-        match index {
-            0 => &mut self.x,
-            1 => &mut self.y,
-            2 => &mut self.z,
-            _ => panic!("Index out of bounds"),
-        }
+    pub fn lerp(&self, other: &Vector3D<U>, t: f32) -> Vector3D<U> {
+        Self::from_data([
+            self.x() + (other.x() - self.x()) * t,
+            self.y() + (other.y() - self.y()) * t,
+            self.z() + (other.z() - self.z()) * t,
+        ])
     }
 }
 
@@ -125,14 +88,19 @@ impl IndexMut<usize> for Vector3D {
 mod tests {
     use super::*;
 
+    // Two arbitrary spaces, used to check that cast_unit moves a vector
+    // from one unit to another with its components unchanged.
+    pub struct WorldSpace;
+    pub struct LocalSpace;
+
     #[test]
     fn test_add() {
         let v1 = Vector3D::new(1.0, 2.0, 3.0);
         let v2 = Vector3D::new(4.0, 5.0, 6.0);
         let v3 = &v1 + &v2;
-        assert_eq!(v3.x, 5.0);
-        assert_eq!(v3.y, 7.0);
-        assert_eq!(v3.z, 9.0);
+        assert_eq!(v3.x(), 5.0);
+        assert_eq!(v3.y(), 7.0);
+        assert_eq!(v3.z(), 9.0);
     }
 
     #[test]
@@ -140,45 +108,78 @@ mod tests {
         let v1 = Vector3D::new(1.0, 2.0, 3.0);
         let v2 = Vector3D::new(4.0, 5.0, 6.0);
         let v3 = v1 - v2;
-        assert_eq!(v3.x, -3.0);
-        assert_eq!(v3.y, -3.0);
-        assert_eq!(v3.z, -3.0);
+        assert_eq!(v3.x(), -3.0);
+        assert_eq!(v3.y(), -3.0);
+        assert_eq!(v3.z(), -3.0);
+    }
+
+    #[test]
+    fn test_add_owned_and_ref_owned() {
+        let v1 = Vector3D::new(1.0, 2.0, 3.0);
+        let v2 = Vector3D::new(4.0, 5.0, 6.0);
+        assert_eq!(
+            Vector3D::new(1.0, 2.0, 3.0) + Vector3D::new(4.0, 5.0, 6.0),
+            Vector3D::new(5.0, 7.0, 9.0)
+        );
+        assert_eq!(&v1 + v2, Vector3D::new(5.0, 7.0, 9.0));
+    }
+
+    #[test]
+    fn test_sub_ref_ref_and_ref_owned() {
+        let v1 = Vector3D::new(4.0, 5.0, 6.0);
+        let v2 = Vector3D::new(1.0, 2.0, 3.0);
+        assert_eq!(&v1 - &v2, Vector3D::new(3.0, 3.0, 3.0));
+        assert_eq!(&v1 - Vector3D::new(1.0, 2.0, 3.0), Vector3D::new(3.0, 3.0, 3.0));
+    }
+
+    #[test]
+    fn test_add_assign() {
+        let mut v1 = Vector3D::new(1.0, 2.0, 3.0);
+        v1 += Vector3D::new(4.0, 5.0, 6.0);
+        assert_eq!(v1, Vector3D::new(5.0, 7.0, 9.0));
+    }
+
+    #[test]
+    fn test_sub_assign() {
+        let mut v1 = Vector3D::new(4.0, 5.0, 6.0);
+        v1 -= Vector3D::new(1.0, 2.0, 3.0);
+        assert_eq!(v1, Vector3D::new(3.0, 3.0, 3.0));
     }
 
     #[test]
     fn test_neg() {
         let v1 = Vector3D::new(1.0, 2.0, 3.0);
         let v2 = -v1;
-        assert_eq!(v2.x, -1.0);
-        assert_eq!(v2.y, -2.0);
-        assert_eq!(v2.z, -3.0);
+        assert_eq!(v2.x(), -1.0);
+        assert_eq!(v2.y(), -2.0);
+        assert_eq!(v2.z(), -3.0);
     }
 
     #[test]
     fn test_mul() {
         let v1 = Vector3D::new(1.0, 2.0, 3.0);
         let v2 = &v1 * 2.0;
-        assert_eq!(v2.x, 2.0);
-        assert_eq!(v2.y, 4.0);
-        assert_eq!(v2.z, 6.0);
+        assert_eq!(v2.x(), 2.0);
+        assert_eq!(v2.y(), 4.0);
+        assert_eq!(v2.z(), 6.0);
     }
 
     #[test]
     fn test_mul_assign() {
         let mut v1 = Vector3D::new(1.0, 2.0, 3.0);
         v1 *= 2.0;
-        assert_eq!(v1.x, 2.0);
-        assert_eq!(v1.y, 4.0);
-        assert_eq!(v1.z, 6.0);
+        assert_eq!(v1.x(), 2.0);
+        assert_eq!(v1.y(), 4.0);
+        assert_eq!(v1.z(), 6.0);
     }
 
     #[test]
     fn test_div() {
         let v1 = Vector3D::new(1.0, 2.0, 3.0);
         let v2 = &v1 / 2.0;
-        assert_eq!(v2.x, 0.5);
-        assert_eq!(v2.y, 1.0);
-        assert_eq!(v2.z, 1.5);
+        assert_eq!(v2.x(), 0.5);
+        assert_eq!(v2.y(), 1.0);
+        assert_eq!(v2.z(), 1.5);
     }
 
     #[test]
@@ -187,18 +188,18 @@ mod tests {
         let v2 = &v1 / 0.0;
 
         // Going by IEEE 754, dividing by zero results in infinity
-        assert_eq!(v2.x, f32::INFINITY);
-        assert_eq!(v2.y, f32::INFINITY);
-        assert_eq!(v2.z, f32::INFINITY);
+        assert_eq!(v2.x(), f32::INFINITY);
+        assert_eq!(v2.y(), f32::INFINITY);
+        assert_eq!(v2.z(), f32::INFINITY);
     }
 
     #[test]
     fn test_div_assign() {
         let mut v1 = Vector3D::new(1.0, 2.0, 3.0);
         v1 /= 2.0;
-        assert_eq!(v1.x, 0.5);
-        assert_eq!(v1.y, 1.0);
-        assert_eq!(v1.z, 1.5);
+        assert_eq!(v1.x(), 0.5);
+        assert_eq!(v1.y(), 1.0);
+        assert_eq!(v1.z(), 1.5);
     }
 
     #[test]
@@ -236,9 +237,9 @@ mod tests {
         let v2 = Vector3D::new(4.0, 5.0, 6.0);
         let v3 = &v1 + &v2;
         let v4 = &v3 * 2.0;
-        assert_eq!(v4.x, 10.0);
-        assert_eq!(v4.y, 14.0);
-        assert_eq!(v4.z, 18.0);
+        assert_eq!(v4.x(), 10.0);
+        assert_eq!(v4.y(), 14.0);
+        assert_eq!(v4.z(), 18.0);
 
         let v5 = &(&v1 + &v2) * 2.0;
         assert_eq!(v5, v4);
@@ -262,4 +263,74 @@ mod tests {
         let v2 = Vector3D::new(0.26726124, 0.5345225, 0.8017837);
         assert_eq!(v1.normalize(), v2);
     }
+
+    #[test]
+    fn test_dot() {
+        let v1 = Vector3D::new(1.0, 2.0, 3.0);
+        let v2 = Vector3D::new(4.0, 5.0, 6.0);
+        assert_eq!(v1.dot(&v2), 32.0);
+    }
+
+    #[test]
+    fn test_cross() {
+        let v1 = Vector3D::new(1.0, 0.0, 0.0);
+        let v2 = Vector3D::new(0.0, 1.0, 0.0);
+        assert_eq!(v1.cross(&v2), Vector3D::new(0.0, 0.0, 1.0));
+    }
+
+    #[test]
+    fn test_distance_squared() {
+        let v1 = Vector3D::new(1.0, 2.0, 3.0);
+        let v2 = Vector3D::new(4.0, 6.0, 3.0);
+        assert_eq!(v1.distance_squared(&v2), 25.0);
+    }
+
+    #[test]
+    fn test_distance() {
+        let v1 = Vector3D::new(1.0, 2.0, 3.0);
+        let v2 = Vector3D::new(4.0, 6.0, 3.0);
+        assert_eq!(v1.distance(&v2), 5.0);
+    }
+
+    #[test]
+    fn test_angle_between() {
+        let v1 = Vector3D::new(1.0, 0.0, 0.0);
+        let v2 = Vector3D::new(0.0, 1.0, 0.0);
+        assert_eq!(v1.angle_between(&v2), std::f32::consts::FRAC_PI_2);
+
+        let v3 = Vector3D::new(2.0, 0.0, 0.0);
+        assert_eq!(v1.angle_between(&v3), 0.0);
+    }
+
+    #[test]
+    fn test_project_onto() {
+        let v1 = Vector3D::new(2.0, 2.0, 0.0);
+        let v2 = Vector3D::new(1.0, 0.0, 0.0);
+        assert_eq!(v1.project_onto(&v2), Vector3D::new(2.0, 0.0, 0.0));
+    }
+
+    #[test]
+    fn test_reflect() {
+        let v1 = Vector3D::new(1.0, -1.0, 0.0);
+        let normal = Vector3D::new(0.0, 1.0, 0.0);
+        assert_eq!(v1.reflect(&normal), Vector3D::new(1.0, 1.0, 0.0));
+    }
+
+    #[test]
+    fn test_lerp() {
+        let v1 = Vector3D::new(0.0, 0.0, 0.0);
+        let v2 = Vector3D::new(10.0, 10.0, 10.0);
+        assert_eq!(v1.lerp(&v2, 0.5), Vector3D::new(5.0, 5.0, 5.0));
+    }
+
+    #[test]
+    fn test_cast_unit() {
+        // `new` is only inherent on the unit-less `Vector3D<Unknown>`, so a
+        // unit-tagged vector is built via the generic `from_data` instead.
+        let world: Vector3D<WorldSpace> = Vector3D::from_data([1.0, 2.0, 3.0]);
+        let local: Vector3D<LocalSpace> = world.cast_unit();
+        assert_eq!(local.x(), 1.0);
+        assert_eq!(local.y(), 2.0);
+        assert_eq!(local.z(), 3.0);
+    }
 }